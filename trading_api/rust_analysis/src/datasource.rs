@@ -0,0 +1,51 @@
+use crate::series::OHLCVSeries;
+use yahoo_finance_api as yahoo;
+
+#[derive(Debug)]
+pub enum DataSourceError {
+    Provider(String),
+    NoData,
+}
+
+impl std::fmt::Display for DataSourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataSourceError::Provider(msg) => write!(f, "provider error: {}", msg),
+            DataSourceError::NoData => write!(f, "no data returned for symbol"),
+        }
+    }
+}
+
+/// Fetch OHLCV bars for a symbol from Yahoo Finance over the given interval/range
+pub async fn fetch_ohlc(symbol: &str, interval: &str, range: &str) -> Result<OHLCVSeries, DataSourceError> {
+    let provider = yahoo::YahooConnector::new().map_err(|e| DataSourceError::Provider(e.to_string()))?;
+
+    let response = provider
+        .get_quote_range(symbol, interval, range)
+        .await
+        .map_err(|e| DataSourceError::Provider(e.to_string()))?;
+
+    let quotes = response
+        .quotes()
+        .map_err(|e| DataSourceError::Provider(e.to_string()))?;
+
+    if quotes.is_empty() {
+        return Err(DataSourceError::NoData);
+    }
+
+    let mut open = Vec::with_capacity(quotes.len());
+    let mut high = Vec::with_capacity(quotes.len());
+    let mut low = Vec::with_capacity(quotes.len());
+    let mut close = Vec::with_capacity(quotes.len());
+    let mut volume = Vec::with_capacity(quotes.len());
+
+    for quote in quotes {
+        open.push(quote.open);
+        high.push(quote.high);
+        low.push(quote.low);
+        close.push(quote.close);
+        volume.push(quote.volume as f64);
+    }
+
+    Ok(OHLCVSeries { open, high, low, close, volume })
+}
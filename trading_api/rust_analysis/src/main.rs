@@ -2,17 +2,29 @@ use actix_web::{web, App, HttpResponse, HttpServer, Responder};
 use actix_cors::Cors;
 use serde::{Deserialize, Serialize};
 
+mod datasource;
 mod indicators;
 mod patterns;
+mod risk;
+mod series;
 mod smc;
 mod sr_zones;
+mod strategy;
+
+use series::{OHLCVSeries, PriceSource};
 
 #[derive(Deserialize)]
 struct IndicatorRequest {
     prices: Vec<f64>,
+    #[serde(default)]
+    open: Vec<f64>,
     high: Vec<f64>,
     low: Vec<f64>,
     close: Vec<f64>,
+    #[serde(default)]
+    volume: Vec<f64>,
+    #[serde(default)]
+    price_source: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -23,18 +35,57 @@ struct IndicatorResponse {
     atr: Vec<f64>,
 }
 
+fn build_indicator_response(series: &OHLCVSeries, price_source: Option<&str>, fallback_prices: &[f64]) -> IndicatorResponse {
+    // ohlc4 needs a matching `open` column; without one (e.g. the default
+    // empty vec from a request that omitted it), fall back to close rather
+    // than silently zipping against nothing and returning empty arrays.
+    let prices = match price_source {
+        Some("hl2") => series.price_source(PriceSource::Hl2),
+        Some("hlc3") => series.price_source(PriceSource::Hlc3),
+        Some("hlcc4") => series.price_source(PriceSource::Hlcc4),
+        Some("ohlc4") if series.open.len() == series.close.len() => {
+            series.price_source(PriceSource::Ohlc4)
+        }
+        Some("ohlc4") => series.close.clone(),
+        _ => fallback_prices.to_vec(),
+    };
+
+    IndicatorResponse {
+        ema_50: indicators::calculate_ema(&prices, 50),
+        ema_200: indicators::calculate_ema(&prices, 200),
+        rsi: indicators::calculate_rsi(&prices, 14),
+        atr: indicators::calculate_atr(&series.high, &series.low, &series.close, 14),
+    }
+}
+
 async fn calculate_indicators(req: web::Json<IndicatorRequest>) -> impl Responder {
-    let ema_50 = indicators::calculate_ema(&req.prices, 50);
-    let ema_200 = indicators::calculate_ema(&req.prices, 200);
-    let rsi = indicators::calculate_rsi(&req.prices, 14);
-    let atr = indicators::calculate_atr(&req.high, &req.low, &req.close, 14);
-    
-    HttpResponse::Ok().json(IndicatorResponse {
-        ema_50,
-        ema_200,
-        rsi,
-        atr,
-    })
+    let series = OHLCVSeries {
+        open: req.open.clone(),
+        high: req.high.clone(),
+        low: req.low.clone(),
+        close: req.close.clone(),
+        volume: req.volume.clone(),
+    };
+
+    let response = build_indicator_response(&series, req.price_source.as_deref(), &req.prices);
+    HttpResponse::Ok().json(response)
+}
+
+async fn calculate_indicators_by_symbol(path: web::Path<String>) -> impl Responder {
+    let symbol = path.into_inner();
+    match datasource::fetch_ohlc(&symbol, "1d", "6mo").await {
+        Ok(series) => {
+            let close = series.close.clone();
+            let response = build_indicator_response(&series, None, &close);
+            HttpResponse::Ok().json(response)
+        }
+        Err(datasource::DataSourceError::NoData) => {
+            HttpResponse::NotFound().json(serde_json::json!({ "error": "no data for symbol" }))
+        }
+        Err(datasource::DataSourceError::Provider(msg)) => {
+            HttpResponse::BadGateway().json(serde_json::json!({ "error": msg }))
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -43,6 +94,8 @@ struct OHLC {
     high: f64,
     low: f64,
     close: f64,
+    #[serde(default)]
+    volume: f64,
 }
 
 #[derive(Deserialize)]
@@ -61,6 +114,9 @@ struct PatternResponse {
     gravestone_doji: Vec<bool>,
     morning_star: Vec<bool>,
     evening_star: Vec<bool>,
+    marubozu_bullish: Vec<bool>,
+    marubozu_bearish: Vec<bool>,
+    doji: Vec<bool>,
 }
 
 async fn detect_patterns(req: web::Json<PatternRequest>) -> impl Responder {
@@ -73,7 +129,9 @@ async fn detect_patterns(req: web::Json<PatternRequest>) -> impl Responder {
     let gravestone_doji = patterns::detect_gravestone_doji(&req.ohlc);
     let morning_star = patterns::detect_morning_star(&req.ohlc);
     let evening_star = patterns::detect_evening_star(&req.ohlc);
-    
+    let (marubozu_bullish, marubozu_bearish) = patterns::detect_marubozu(&req.ohlc);
+    let doji = patterns::detect_doji(&req.ohlc);
+
     HttpResponse::Ok().json(PatternResponse {
         hammer,
         inverted_hammer,
@@ -84,6 +142,9 @@ async fn detect_patterns(req: web::Json<PatternRequest>) -> impl Responder {
         gravestone_doji,
         morning_star,
         evening_star,
+        marubozu_bullish,
+        marubozu_bearish,
+        doji,
     })
 }
 
@@ -100,34 +161,61 @@ struct SmcResponse {
     fvg_zones: Vec<smc::FvgZone>,
     ob_zones: Vec<smc::OrderBlockZone>,
     sr_zones: Vec<sr_zones::SrZone>,
+    structure_events: Vec<smc::StructureEvent>,
+    vwap: Vec<f64>,
 }
 
-async fn analyze_smc(req: web::Json<PatternRequest>) -> impl Responder {
-    let high: Vec<f64> = req.ohlc.iter().map(|x| x.high).collect();
-    let low: Vec<f64> = req.ohlc.iter().map(|x| x.low).collect();
-    let close: Vec<f64> = req.ohlc.iter().map(|x| x.close).collect();
-    let current_price = req.ohlc.last().map(|x| x.close).unwrap_or(0.0);
-    
-    let (swing_highs, swing_lows) = smc::identify_swing_points(&high, &low, 5);
-    
+fn ohlc_to_series(ohlc: &[OHLC]) -> OHLCVSeries {
+    OHLCVSeries {
+        open: ohlc.iter().map(|x| x.open).collect(),
+        high: ohlc.iter().map(|x| x.high).collect(),
+        low: ohlc.iter().map(|x| x.low).collect(),
+        close: ohlc.iter().map(|x| x.close).collect(),
+        volume: ohlc.iter().map(|x| x.volume).collect(),
+    }
+}
+
+fn series_to_ohlc(series: &OHLCVSeries) -> Vec<OHLC> {
+    (0..series.close.len())
+        .map(|i| OHLC {
+            open: series.open[i],
+            high: series.high[i],
+            low: series.low[i],
+            close: series.close[i],
+            volume: series.volume[i],
+        })
+        .collect()
+}
+
+fn build_smc_response(ohlc: &[OHLC]) -> SmcResponse {
+    let series = ohlc_to_series(ohlc);
+    let current_price = series.close.last().copied().unwrap_or(0.0);
+
+    let (swing_highs, swing_lows) = smc::identify_swing_points(&series.high, &series.low, 5);
+
     // Get both boolean and zone data
-    let (fvg_bullish, fvg_bearish) = smc::detect_fvg(&req.ohlc);
-    let fvg_zones = smc::detect_fvg_zones(&req.ohlc);
-    
-    let (ob_bullish, ob_bearish) = smc::detect_order_blocks(&req.ohlc);
-    let ob_zones = smc::detect_order_block_zones(&req.ohlc);
-    
-    let (sweep_bullish, sweep_bearish) = smc::detect_liquidity_sweep(&high, &low, &close, 20);
-    
+    let (fvg_bullish, fvg_bearish) = smc::detect_fvg(ohlc);
+    let fvg_zones = smc::detect_fvg_zones(ohlc);
+
+    let (ob_bullish, ob_bearish) = smc::detect_order_blocks(ohlc);
+    let ob_zones = smc::detect_order_block_zones(ohlc);
+
+    let (sweep_bullish, sweep_bearish) =
+        smc::detect_liquidity_sweep(&series.high, &series.low, &series.close, 20);
+
+    let structure_events = smc::detect_market_structure(&series.high, &series.low, &series.close, 5);
+
     let zones = sr_zones::identify_sr_zones(
-        &swing_highs, 
-        &swing_lows, 
-        current_price, 
-        0.002, 
+        &swing_highs,
+        &swing_lows,
+        current_price,
+        0.002,
         2
     );
-    
-    HttpResponse::Ok().json(SmcResponse {
+
+    let vwap = series::calculate_vwap(&series);
+
+    SmcResponse {
         swing_highs,
         swing_lows,
         fvg_bullish,
@@ -139,7 +227,155 @@ async fn analyze_smc(req: web::Json<PatternRequest>) -> impl Responder {
         fvg_zones,
         ob_zones,
         sr_zones: zones,
-    })
+        structure_events,
+        vwap,
+    }
+}
+
+async fn analyze_smc(req: web::Json<PatternRequest>) -> impl Responder {
+    HttpResponse::Ok().json(build_smc_response(&req.ohlc))
+}
+
+async fn analyze_smc_by_symbol(path: web::Path<String>) -> impl Responder {
+    let symbol = path.into_inner();
+    match datasource::fetch_ohlc(&symbol, "1d", "6mo").await {
+        Ok(series) => {
+            let ohlc = series_to_ohlc(&series);
+            HttpResponse::Ok().json(build_smc_response(&ohlc))
+        }
+        Err(datasource::DataSourceError::NoData) => {
+            HttpResponse::NotFound().json(serde_json::json!({ "error": "no data for symbol" }))
+        }
+        Err(datasource::DataSourceError::Provider(msg)) => {
+            HttpResponse::BadGateway().json(serde_json::json!({ "error": msg }))
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct StrategyRequest {
+    prices: Vec<f64>,
+    fast: usize,
+    slow: usize,
+    rsi_period: usize,
+    trend_period: usize,
+    #[serde(default = "default_oversold")]
+    oversold: f64,
+    #[serde(default = "default_overbought")]
+    overbought: f64,
+}
+
+fn default_oversold() -> f64 {
+    30.0
+}
+
+fn default_overbought() -> f64 {
+    70.0
+}
+
+#[derive(Serialize)]
+struct StrategyResponse {
+    signals: Vec<strategy::Signal>,
+}
+
+async fn analyze_strategy(req: web::Json<StrategyRequest>) -> impl Responder {
+    let signals = strategy::generate_trend_signals(
+        &req.prices,
+        req.fast,
+        req.slow,
+        req.rsi_period,
+        req.trend_period,
+        req.oversold,
+        req.overbought,
+    );
+
+    HttpResponse::Ok().json(StrategyResponse { signals })
+}
+
+#[derive(Deserialize)]
+struct RiskRequest {
+    entry: f64,
+    direction: String,
+    atr: f64,
+    account_balance: f64,
+    risk_pct: f64,
+    rr_ratio: f64,
+    #[serde(default = "default_atr_multiplier")]
+    atr_multiplier: f64,
+}
+
+fn default_atr_multiplier() -> f64 {
+    1.5
+}
+
+async fn calculate_risk(req: web::Json<RiskRequest>) -> impl Responder {
+    let levels = risk::compute_trade_levels(
+        req.entry,
+        &req.direction,
+        req.atr,
+        req.account_balance,
+        req.risk_pct,
+        req.rr_ratio,
+        req.atr_multiplier,
+    );
+
+    HttpResponse::Ok().json(levels)
+}
+
+#[derive(Deserialize)]
+struct TrailingStopRequest {
+    current_price: f64,
+    direction: String,
+    atr: f64,
+    current_stop: f64,
+    #[serde(default = "default_atr_multiplier")]
+    atr_multiplier: f64,
+}
+
+#[derive(Serialize)]
+struct TrailingStopResponse {
+    stop: f64,
+}
+
+async fn calculate_trailing_stop(req: web::Json<TrailingStopRequest>) -> impl Responder {
+    let stop = risk::trailing_stop(
+        req.current_price,
+        &req.direction,
+        req.atr,
+        req.atr_multiplier,
+        req.current_stop,
+    );
+
+    HttpResponse::Ok().json(TrailingStopResponse { stop })
+}
+
+#[derive(Deserialize)]
+struct FetchOhlcRequest {
+    symbol: String,
+    #[serde(default = "default_interval")]
+    interval: String,
+    #[serde(default = "default_range")]
+    range: String,
+}
+
+fn default_interval() -> String {
+    "1d".to_string()
+}
+
+fn default_range() -> String {
+    "6mo".to_string()
+}
+
+async fn fetch_ohlc(req: web::Json<FetchOhlcRequest>) -> impl Responder {
+    match datasource::fetch_ohlc(&req.symbol, &req.interval, &req.range).await {
+        Ok(series) => HttpResponse::Ok().json(series),
+        Err(datasource::DataSourceError::NoData) => {
+            HttpResponse::NotFound().json(serde_json::json!({ "error": "no data for symbol" }))
+        }
+        Err(datasource::DataSourceError::Provider(msg)) => {
+            HttpResponse::BadGateway().json(serde_json::json!({ "error": msg }))
+        }
+    }
 }
 
 async fn health_check() -> impl Responder {
@@ -164,6 +400,12 @@ async fn main() -> std::io::Result<()> {
             .route("/calculate/indicators", web::post().to(calculate_indicators))
             .route("/detect/patterns", web::post().to(detect_patterns))
             .route("/analyze/smc", web::post().to(analyze_smc))
+            .route("/analyze/strategy", web::post().to(analyze_strategy))
+            .route("/calculate/risk", web::post().to(calculate_risk))
+            .route("/calculate/trailing-stop", web::post().to(calculate_trailing_stop))
+            .route("/fetch/ohlc", web::post().to(fetch_ohlc))
+            .route("/analyze/smc/{symbol}", web::get().to(analyze_smc_by_symbol))
+            .route("/calculate/indicators/{symbol}", web::get().to(calculate_indicators_by_symbol))
     })
     .bind(("127.0.0.1", 8001))?
     .run()
@@ -1,24 +1,65 @@
+use crate::indicators;
 use crate::OHLC;
 
+const SHADOW_SIGNIFICANCE_PCT: f64 = 0.05;
+const LONG_WICK_MULTIPLIER: f64 = 2.0;
+
+/// Rolling body-size baseline used to classify a candle's body as "small" or
+/// "long" relative to recent volatility, rather than against a fixed ratio.
+fn body_baseline(ohlc: &[OHLC]) -> Vec<f64> {
+    let bodies: Vec<f64> = ohlc.iter().map(|c| (c.close - c.open).abs()).collect();
+    indicators::calculate_ema(&bodies, 14)
+}
+
+/// The EMA baseline is 0.0 until it warms up (see `calculate_ema`); body
+/// classification is meaningless before that and must be skipped.
+fn is_baseline_warm(body_avg: f64) -> bool {
+    body_avg > 0.0
+}
+
+fn is_small_body(body: f64, body_avg: f64) -> bool {
+    is_baseline_warm(body_avg) && body < body_avg
+}
+
+fn is_long_body(body: f64, body_avg: f64) -> bool {
+    is_baseline_warm(body_avg) && body > body_avg
+}
+
+/// A shadow counts as significant once it exceeds `pct` of the candle's body
+fn is_significant_shadow(shadow: f64, body: f64, pct: f64) -> bool {
+    shadow > body * pct
+}
+
+/// A shadow counts as the dominant "long" wick once it exceeds the body by
+/// `LONG_WICK_MULTIPLIER` — the 5% significance test alone is too loose to
+/// capture "long" on its own.
+fn is_long_shadow(shadow: f64, body: f64) -> bool {
+    shadow > body * LONG_WICK_MULTIPLIER
+}
+
 /// Detect Hammer pattern
 pub fn detect_hammer(ohlc: &[OHLC]) -> Vec<bool> {
     let len = ohlc.len();
     let mut result = vec![false; len];
-    
+    let body_avg = body_baseline(ohlc);
+
     for i in 0..len {
         let body = (ohlc[i].close - ohlc[i].open).abs();
         let upper_wick = ohlc[i].high - ohlc[i].close.max(ohlc[i].open);
         let lower_wick = ohlc[i].close.min(ohlc[i].open) - ohlc[i].low;
         let total_range = ohlc[i].high - ohlc[i].low;
-        
+
         if total_range > 0.0 {
-            // Hammer: small body, long lower wick, small upper wick
-            if lower_wick > body * 2.0 && upper_wick < body * 0.5 {
+            // Hammer: small body (vs baseline), long lower wick, small upper wick
+            if is_small_body(body, body_avg[i])
+                && is_long_shadow(lower_wick, body)
+                && !is_significant_shadow(upper_wick, body, SHADOW_SIGNIFICANCE_PCT)
+            {
                 result[i] = true;
             }
         }
     }
-    
+
     result
 }
 
@@ -26,21 +67,77 @@ pub fn detect_hammer(ohlc: &[OHLC]) -> Vec<bool> {
 pub fn detect_inverted_hammer(ohlc: &[OHLC]) -> Vec<bool> {
     let len = ohlc.len();
     let mut result = vec![false; len];
-    
+    let body_avg = body_baseline(ohlc);
+
     for i in 0..len {
         let body = (ohlc[i].close - ohlc[i].open).abs();
         let upper_wick = ohlc[i].high - ohlc[i].close.max(ohlc[i].open);
         let lower_wick = ohlc[i].close.min(ohlc[i].open) - ohlc[i].low;
         let total_range = ohlc[i].high - ohlc[i].low;
-        
+
         if total_range > 0.0 {
-            // Inverted Hammer: small body, long upper wick, small lower wick
-            if upper_wick > body * 2.0 && lower_wick < body * 0.5 {
+            // Inverted Hammer: small body (vs baseline), long upper wick, small lower wick
+            if is_small_body(body, body_avg[i])
+                && is_long_shadow(upper_wick, body)
+                && !is_significant_shadow(lower_wick, body, SHADOW_SIGNIFICANCE_PCT)
+            {
                 result[i] = true;
             }
         }
     }
-    
+
+    result
+}
+
+/// Detect Marubozu pattern: a long body with near-zero upper and lower shadows
+pub fn detect_marubozu(ohlc: &[OHLC]) -> (Vec<bool>, Vec<bool>) {
+    let len = ohlc.len();
+    let mut bullish = vec![false; len];
+    let mut bearish = vec![false; len];
+    let body_avg = body_baseline(ohlc);
+
+    for i in 0..len {
+        let body = (ohlc[i].close - ohlc[i].open).abs();
+        let upper_wick = ohlc[i].high - ohlc[i].close.max(ohlc[i].open);
+        let lower_wick = ohlc[i].close.min(ohlc[i].open) - ohlc[i].low;
+        let total_range = ohlc[i].high - ohlc[i].low;
+
+        if total_range > 0.0
+            && is_long_body(body, body_avg[i])
+            && !is_significant_shadow(upper_wick, body, SHADOW_SIGNIFICANCE_PCT)
+            && !is_significant_shadow(lower_wick, body, SHADOW_SIGNIFICANCE_PCT)
+        {
+            if ohlc[i].close > ohlc[i].open {
+                bullish[i] = true;
+            } else if ohlc[i].close < ohlc[i].open {
+                bearish[i] = true;
+            }
+        }
+    }
+
+    (bullish, bearish)
+}
+
+/// Detect a strict Doji: body within 5% of the high-low range with roughly
+/// symmetric upper/lower shadows
+pub fn detect_doji(ohlc: &[OHLC]) -> Vec<bool> {
+    let len = ohlc.len();
+    let mut result = vec![false; len];
+
+    for i in 0..len {
+        let body = (ohlc[i].close - ohlc[i].open).abs();
+        let upper_wick = ohlc[i].high - ohlc[i].close.max(ohlc[i].open);
+        let lower_wick = ohlc[i].close.min(ohlc[i].open) - ohlc[i].low;
+        let total_range = ohlc[i].high - ohlc[i].low;
+
+        if total_range > 0.0 {
+            let symmetric_shadows = (upper_wick - lower_wick).abs() < 0.1 * total_range;
+            if body <= 0.05 * total_range && symmetric_shadows {
+                result[i] = true;
+            }
+        }
+    }
+
     result
 }
 
@@ -55,15 +152,15 @@ pub fn detect_hanging_man(ohlc: &[OHLC]) -> Vec<bool> {
 pub fn detect_dragonfly_doji(ohlc: &[OHLC]) -> Vec<bool> {
     let len = ohlc.len();
     let mut result = vec![false; len];
-    
+
     for i in 0..len {
         let body = (ohlc[i].close - ohlc[i].open).abs();
         let upper_wick = ohlc[i].high - ohlc[i].close.max(ohlc[i].open);
         let lower_wick = ohlc[i].close.min(ohlc[i].open) - ohlc[i].low;
         let total_range = ohlc[i].high - ohlc[i].low;
-        
+
         if total_range > 0.0 {
-            // Dragonfly: tiny body, long lower wick, no upper wick
+            // Dragonfly: tiny body vs range, long lower wick, no upper wick
             if body < 0.05 * total_range && lower_wick > 0.7 * total_range && upper_wick < 0.05 * total_range {
                 result[i] = true;
             }
@@ -76,15 +173,15 @@ pub fn detect_dragonfly_doji(ohlc: &[OHLC]) -> Vec<bool> {
 pub fn detect_gravestone_doji(ohlc: &[OHLC]) -> Vec<bool> {
     let len = ohlc.len();
     let mut result = vec![false; len];
-    
+
     for i in 0..len {
         let body = (ohlc[i].close - ohlc[i].open).abs();
         let upper_wick = ohlc[i].high - ohlc[i].close.max(ohlc[i].open);
         let lower_wick = ohlc[i].close.min(ohlc[i].open) - ohlc[i].low;
         let total_range = ohlc[i].high - ohlc[i].low;
-        
+
         if total_range > 0.0 {
-            // Gravestone: tiny body, long upper wick, no lower wick
+            // Gravestone: tiny body vs range, long upper wick, no lower wick
             if body < 0.05 * total_range && upper_wick > 0.7 * total_range && lower_wick < 0.05 * total_range {
                 result[i] = true;
             }
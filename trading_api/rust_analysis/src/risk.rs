@@ -0,0 +1,67 @@
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TradeLevels {
+    pub stop_loss: f64,
+    pub take_profit: f64,
+    pub position_size: f64,
+    pub risk_reward: f64,
+}
+
+/// Derive a full trade plan from an ATR-based stop distance.
+///
+/// The stop sits `atr * atr_multiplier` away from entry (below for longs,
+/// above for shorts); the take-profit extends that same distance times
+/// `rr_ratio` in the trade's favor. Position size is solved so that a stop
+/// out loses exactly `account_balance * risk_pct`.
+pub fn compute_trade_levels(
+    entry: f64,
+    direction: &str,
+    atr: f64,
+    account_balance: f64,
+    risk_pct: f64,
+    rr_ratio: f64,
+    atr_multiplier: f64,
+) -> TradeLevels {
+    let stop_distance = atr * atr_multiplier;
+
+    let (stop_loss, take_profit) = if direction == "short" {
+        (entry + stop_distance, entry - stop_distance * rr_ratio)
+    } else {
+        (entry - stop_distance, entry + stop_distance * rr_ratio)
+    };
+
+    let risk_amount = account_balance * risk_pct;
+    let position_size = if stop_distance > 0.0 {
+        risk_amount / stop_distance
+    } else {
+        0.0
+    };
+
+    TradeLevels {
+        stop_loss,
+        take_profit,
+        position_size,
+        risk_reward: rr_ratio,
+    }
+}
+
+/// Ratchet a stop loss by ATR as price moves favorably; the stop only ever
+/// tightens toward price, never loosens.
+pub fn trailing_stop(
+    current_price: f64,
+    direction: &str,
+    atr: f64,
+    atr_multiplier: f64,
+    current_stop: f64,
+) -> f64 {
+    let trail_distance = atr * atr_multiplier;
+
+    if direction == "short" {
+        let candidate = current_price + trail_distance;
+        candidate.min(current_stop)
+    } else {
+        let candidate = current_price - trail_distance;
+        candidate.max(current_stop)
+    }
+}
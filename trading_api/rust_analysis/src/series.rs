@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+
+/// Central OHLCV container shared across analyzers, replacing the parallel
+/// high/low/close vectors each handler used to collect on its own.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OHLCVSeries {
+    pub open: Vec<f64>,
+    pub high: Vec<f64>,
+    pub low: Vec<f64>,
+    pub close: Vec<f64>,
+    pub volume: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSource {
+    Close,
+    Hl2,
+    Hlc3,
+    Ohlc4,
+    Hlcc4,
+}
+
+impl OHLCVSeries {
+    /// (high + low) / 2
+    pub fn hl2(&self) -> Vec<f64> {
+        self.high
+            .iter()
+            .zip(&self.low)
+            .map(|(h, l)| (h + l) / 2.0)
+            .collect()
+    }
+
+    /// (high + low + close) / 3
+    pub fn hlc3(&self) -> Vec<f64> {
+        self.high
+            .iter()
+            .zip(&self.low)
+            .zip(&self.close)
+            .map(|((h, l), c)| (h + l + c) / 3.0)
+            .collect()
+    }
+
+    /// (open + high + low + close) / 4
+    pub fn ohlc4(&self) -> Vec<f64> {
+        self.open
+            .iter()
+            .zip(&self.high)
+            .zip(&self.low)
+            .zip(&self.close)
+            .map(|(((o, h), l), c)| (o + h + l + c) / 4.0)
+            .collect()
+    }
+
+    /// (high + low + 2*close) / 4
+    pub fn hlcc4(&self) -> Vec<f64> {
+        self.high
+            .iter()
+            .zip(&self.low)
+            .zip(&self.close)
+            .map(|((h, l), c)| (h + l + 2.0 * c) / 4.0)
+            .collect()
+    }
+
+    /// Resolve the requested price source so indicator functions that take a
+    /// plain `&[f64]` can be computed on typical price instead of close
+    pub fn price_source(&self, source: PriceSource) -> Vec<f64> {
+        match source {
+            PriceSource::Close => self.close.clone(),
+            PriceSource::Hl2 => self.hl2(),
+            PriceSource::Hlc3 => self.hlc3(),
+            PriceSource::Ohlc4 => self.ohlc4(),
+            PriceSource::Hlcc4 => self.hlcc4(),
+        }
+    }
+}
+
+/// Calculate Volume-Weighted Average Price (VWAP), accumulated over the full series
+pub fn calculate_vwap(series: &OHLCVSeries) -> Vec<f64> {
+    let typical = series.hlc3();
+    let len = typical.len();
+    let mut vwap = vec![0.0; len];
+
+    let mut cum_pv = 0.0;
+    let mut cum_vol = 0.0;
+
+    for i in 0..len {
+        cum_pv += typical[i] * series.volume[i];
+        cum_vol += series.volume[i];
+        vwap[i] = if cum_vol > 0.0 { cum_pv / cum_vol } else { 0.0 };
+    }
+
+    vwap
+}
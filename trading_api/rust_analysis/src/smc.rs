@@ -8,6 +8,9 @@ pub struct FvgZone {
     pub bottom: f64,
     pub index: usize,
     pub gap_size: f64,
+    pub mitigated: bool,
+    pub mitigation_index: Option<usize>,
+    pub active: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -16,6 +19,17 @@ pub struct OrderBlockZone {
     pub top: f64,
     pub bottom: f64,
     pub index: usize,
+    pub mitigated: bool,
+    pub mitigation_index: Option<usize>,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StructureEvent {
+    pub index: usize,
+    pub event_type: String,  // "BOS" or "CHoCH"
+    pub direction: String,   // "bullish" or "bearish"
+    pub level: f64,
 }
 
 /// Identify Swing Highs and Swing Lows
@@ -59,6 +73,71 @@ pub fn identify_swing_points(high: &[f64], low: &[f64], pivot_legs: usize) -> (V
     (swing_highs, swing_lows)
 }
 
+/// Detect Break-of-Structure (BOS) and Change-of-Character (CHoCH) events
+///
+/// Walks bars left to right tracking the most recently *confirmed* swing
+/// high/low (a swing at index `p` is only known once bar `p + pivot_legs`
+/// has printed) and the current trend state. A close breaking the tracked
+/// swing high is a CHoCH when the trend was bearish (reversal) or a BOS
+/// when it was already bullish (continuation); the swing low break mirrors
+/// this for the bearish side.
+pub fn detect_market_structure(
+    high: &[f64],
+    low: &[f64],
+    close: &[f64],
+    pivot_legs: usize,
+) -> Vec<StructureEvent> {
+    let mut events = Vec::new();
+
+    let (swing_highs, swing_lows) = identify_swing_points(high, low, pivot_legs);
+
+    let mut tracked_high: Option<f64> = None;
+    let mut tracked_low: Option<f64> = None;
+    let mut trend = "none";
+
+    for (i, &price) in close.iter().enumerate() {
+        if i >= pivot_legs {
+            let confirmed = i - pivot_legs;
+            if let Some(level) = swing_highs[confirmed] {
+                tracked_high = Some(level);
+            }
+            if let Some(level) = swing_lows[confirmed] {
+                tracked_low = Some(level);
+            }
+        }
+
+        if let Some(level) = tracked_high {
+            if price > level {
+                let event_type = if trend == "bearish" { "CHoCH" } else { "BOS" };
+                events.push(StructureEvent {
+                    index: i,
+                    event_type: event_type.to_string(),
+                    direction: "bullish".to_string(),
+                    level,
+                });
+                trend = "bullish";
+                tracked_high = None;
+            }
+        }
+
+        if let Some(level) = tracked_low {
+            if price < level {
+                let event_type = if trend == "bullish" { "CHoCH" } else { "BOS" };
+                events.push(StructureEvent {
+                    index: i,
+                    event_type: event_type.to_string(),
+                    direction: "bearish".to_string(),
+                    level,
+                });
+                trend = "bearish";
+                tracked_low = None;
+            }
+        }
+    }
+
+    events
+}
+
 /// Detect Fair Value Gaps (FVG) as Zones
 pub fn detect_fvg_zones(ohlc: &[OHLC]) -> Vec<FvgZone> {
     let len = ohlc.len();
@@ -78,9 +157,12 @@ pub fn detect_fvg_zones(ohlc: &[OHLC]) -> Vec<FvgZone> {
                 bottom: ohlc[i-2].high,
                 index: i,
                 gap_size,
+                mitigated: false,
+                mitigation_index: None,
+                active: true,
             });
         }
-        
+
         // Bearish FVG: High[i] < Low[i-2] (gap between candles)
         if ohlc[i].high < ohlc[i-2].low && ohlc[i].close < ohlc[i].open {
             let gap_size = ohlc[i-2].low - ohlc[i].high;
@@ -90,13 +172,50 @@ pub fn detect_fvg_zones(ohlc: &[OHLC]) -> Vec<FvgZone> {
                 bottom: ohlc[i].high,
                 index: i,
                 gap_size,
+                mitigated: false,
+                mitigation_index: None,
+                active: true,
             });
         }
     }
-    
+
+    mark_fvg_mitigation(&mut zones, ohlc);
     zones
 }
 
+/// Scan candles after each zone and flag it mitigated (price traded back
+/// into the gap) or invalidated (a close pushed fully through it without
+/// ever touching the gap). Either outcome retires the zone (`active =
+/// false`), but only a genuine touch counts as `mitigated`.
+fn mark_fvg_mitigation(zones: &mut [FvgZone], ohlc: &[OHLC]) {
+    for zone in zones.iter_mut() {
+        let start = zone.index + 1;
+        for (offset, candle) in ohlc[start..].iter().enumerate() {
+            let k = start + offset;
+            let (touched, invalidated) = if zone.zone_type == "bullish" {
+                let touched = candle.low >= zone.bottom && candle.low <= zone.top;
+                let invalidated = candle.close < zone.bottom;
+                (touched, invalidated)
+            } else {
+                let touched = candle.high >= zone.bottom && candle.high <= zone.top;
+                let invalidated = candle.close > zone.top;
+                (touched, invalidated)
+            };
+
+            if touched {
+                zone.mitigated = true;
+                zone.mitigation_index = Some(k);
+                zone.active = false;
+                break;
+            } else if invalidated {
+                zone.mitigation_index = Some(k);
+                zone.active = false;
+                break;
+            }
+        }
+    }
+}
+
 /// Detect Order Blocks as Zones
 pub fn detect_order_block_zones(ohlc: &[OHLC]) -> Vec<OrderBlockZone> {
     let len = ohlc.len();
@@ -118,13 +237,16 @@ pub fn detect_order_block_zones(ohlc: &[OHLC]) -> Vec<OrderBlockZone> {
                 top: ohlc[i-1].open,      // Top of the red candle
                 bottom: ohlc[i-1].close,  // Bottom of the red candle
                 index: i-1,
+                mitigated: false,
+                mitigation_index: None,
+                active: true,
             });
         }
-        
+
         let prev_is_green = ohlc[i-1].close > ohlc[i-1].open;
         let curr_is_red = ohlc[i].open > ohlc[i].close;
         let bearish_engulf = ohlc[i].close < ohlc[i-1].open;
-        
+
         // Bearish Order Block: Last green candle before bearish engulfing
         if prev_is_green && curr_is_red && bearish_engulf {
             zones.push(OrderBlockZone {
@@ -132,13 +254,55 @@ pub fn detect_order_block_zones(ohlc: &[OHLC]) -> Vec<OrderBlockZone> {
                 top: ohlc[i-1].close,    // Top of the green candle
                 bottom: ohlc[i-1].open,  // Bottom of the green candle
                 index: i-1,
+                mitigated: false,
+                mitigation_index: None,
+                active: true,
             });
         }
     }
-    
+
+    mark_order_block_mitigation(&mut zones, ohlc);
     zones
 }
 
+/// Scan candles after each order block and flag it mitigated (price traded
+/// back into the block) or invalidated (a close pushed fully through it
+/// without ever touching the block). Either outcome retires the zone
+/// (`active = false`), but only a genuine touch counts as `mitigated`.
+///
+/// The block itself sits on the pivot candle at `zone.index`, but that
+/// candle is immediately followed by the impulse/engulfing candle that
+/// *created* the block (`zone.index + 1`), which routinely trades back into
+/// the block body. The scan must start after that impulse candle, not at it.
+fn mark_order_block_mitigation(zones: &mut [OrderBlockZone], ohlc: &[OHLC]) {
+    for zone in zones.iter_mut() {
+        let start = zone.index + 2;
+        for (offset, candle) in ohlc[start..].iter().enumerate() {
+            let k = start + offset;
+            let (touched, invalidated) = if zone.zone_type == "bullish" {
+                let touched = candle.low >= zone.bottom && candle.low <= zone.top;
+                let invalidated = candle.close < zone.bottom;
+                (touched, invalidated)
+            } else {
+                let touched = candle.high >= zone.bottom && candle.high <= zone.top;
+                let invalidated = candle.close > zone.top;
+                (touched, invalidated)
+            };
+
+            if touched {
+                zone.mitigated = true;
+                zone.mitigation_index = Some(k);
+                zone.active = false;
+                break;
+            } else if invalidated {
+                zone.mitigation_index = Some(k);
+                zone.active = false;
+                break;
+            }
+        }
+    }
+}
+
 /// Legacy function for backward compatibility (returns booleans)
 pub fn detect_fvg(ohlc: &[OHLC]) -> (Vec<bool>, Vec<bool>) {
     let zones = detect_fvg_zones(ohlc);
@@ -0,0 +1,89 @@
+use crate::indicators;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Signal {
+    pub index: usize,
+    pub action: String,  // "long", "short" or "flat"
+    pub reason: String,
+}
+
+/// How far back to look for the oversold/overbought dip that a recovery is
+/// judged against — requiring it on the exact crossover bar is too strict,
+/// since the cross and the RSI recovery rarely land on the same candle.
+const RSI_RECOVERY_LOOKBACK: usize = 5;
+
+/// Generate directional trading signals from a dual EMA crossover gated by a
+/// long-term trend EMA and confirmed by RSI momentum.
+///
+/// A long fires on a golden cross (fast EMA crossing above slow EMA) while
+/// price sits above a rising trend EMA and RSI is rising after a dip below
+/// `oversold` within the last `RSI_RECOVERY_LOOKBACK` bars (RSI itself may
+/// still be below `oversold`). A short mirrors this on a death cross below a
+/// falling trend EMA with RSI falling after a push above `overbought` within
+/// the same lookback. The trend-EMA slope
+/// gate exists to suppress false breakouts against the prevailing trend.
+pub fn generate_trend_signals(
+    prices: &[f64],
+    fast: usize,
+    slow: usize,
+    rsi_period: usize,
+    trend_period: usize,
+    oversold: f64,
+    overbought: f64,
+) -> Vec<Signal> {
+    let len = prices.len();
+    let mut signals = Vec::with_capacity(len);
+
+    if len == 0 {
+        return signals;
+    }
+
+    let fast_ema = indicators::calculate_ema(prices, fast);
+    let slow_ema = indicators::calculate_ema(prices, slow);
+    let trend_ema = indicators::calculate_ema(prices, trend_period);
+    let rsi = indicators::calculate_rsi(prices, rsi_period);
+
+    signals.push(Signal {
+        index: 0,
+        action: "flat".to_string(),
+        reason: "insufficient history for crossover comparison".to_string(),
+    });
+
+    for i in 1..len {
+        let golden_cross = fast_ema[i - 1] <= slow_ema[i - 1] && fast_ema[i] > slow_ema[i];
+        let death_cross = fast_ema[i - 1] >= slow_ema[i - 1] && fast_ema[i] < slow_ema[i];
+
+        let trend_rising = trend_ema[i] > trend_ema[i - 1];
+        let trend_falling = trend_ema[i] < trend_ema[i - 1];
+
+        let lookback_start = i.saturating_sub(RSI_RECOVERY_LOOKBACK);
+        let dipped_oversold = rsi[lookback_start..i].iter().any(|&v| v < oversold);
+        let dipped_overbought = rsi[lookback_start..i].iter().any(|&v| v > overbought);
+
+        let rsi_recovering = rsi[i] > rsi[i - 1] && dipped_oversold;
+        let rsi_falling = rsi[i] < rsi[i - 1] && dipped_overbought;
+
+        if golden_cross && prices[i] > trend_ema[i] && trend_rising && rsi_recovering {
+            signals.push(Signal {
+                index: i,
+                action: "long".to_string(),
+                reason: "golden cross above rising trend EMA with RSI recovering from oversold".to_string(),
+            });
+        } else if death_cross && prices[i] < trend_ema[i] && trend_falling && rsi_falling {
+            signals.push(Signal {
+                index: i,
+                action: "short".to_string(),
+                reason: "death cross below falling trend EMA with RSI falling from overbought".to_string(),
+            });
+        } else {
+            signals.push(Signal {
+                index: i,
+                action: "flat".to_string(),
+                reason: "no confirmed directional setup".to_string(),
+            });
+        }
+    }
+
+    signals
+}